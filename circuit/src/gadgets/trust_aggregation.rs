@@ -0,0 +1,291 @@
+use crate::{Chip, CommonConfig, RegionCtx};
+use halo2::{
+	arithmetic::FieldExt,
+	circuit::{AssignedCell, Layouter, Region},
+	plonk::{ConstraintSystem, Error, Expression, Selector},
+	poly::Rotation,
+};
+
+/// A chip that constrains one EigenTrust aggregation step for a single peer
+/// `i` over `N` neighbors: `t_i' = (1 - a) * sum_j(c_ji * t_j) + a * p_i`.
+///
+/// `c_ji`, `t_j`, `a` and `p_i` are all fixed-point encodings of `f64` trust
+/// scores under the shared scaling constant `SCALE` (i.e. the field element
+/// is `round(value * SCALE)`), so every pairwise product carries scale
+/// `SCALE^2`. The output `out` keeps that same `SCALE^2` convention rather
+/// than rescaling back down to `SCALE`, which is what lets the final gate
+/// avoid an extra division and instead only need one multiplication by the
+/// constant `SCALE^-1` (see `configure` and `synthesize` below).
+pub struct TrustAggregationChip<F: FieldExt, const N: usize, const SCALE: u64> {
+	/// Normalized local trust of each neighbor towards `i` (`c_ji`).
+	c: [AssignedCell<F, F>; N],
+	/// Each neighbor's current global trust score (`t_j`).
+	t: [AssignedCell<F, F>; N],
+	/// Pre-trust value of peer `i`.
+	p_i: AssignedCell<F, F>,
+	/// Pre-trust weight (`a`).
+	a: AssignedCell<F, F>,
+}
+
+impl<F: FieldExt, const N: usize, const SCALE: u64> TrustAggregationChip<F, N, SCALE> {
+	/// Constructor for a chip
+	pub fn new(
+		c: [AssignedCell<F, F>; N], t: [AssignedCell<F, F>; N], p_i: AssignedCell<F, F>,
+		a: AssignedCell<F, F>,
+	) -> Self {
+		Self { c, t, p_i, a }
+	}
+
+	/// The inverse of `SCALE` as a field constant, used to bring the degree-2
+	/// term `a * acc_N` back down from `SCALE^3` to `SCALE^2` so it lines up
+	/// with the rest of the final gate.
+	fn scale_inv() -> F {
+		F::from(SCALE).invert().unwrap()
+	}
+}
+
+impl<F: FieldExt, const N: usize, const SCALE: u64> Chip<F> for TrustAggregationChip<F, N, SCALE> {
+	type Output = AssignedCell<F, F>;
+
+	fn configure(common: &CommonConfig, meta: &mut ConstraintSystem<F>) -> Selector {
+		let aggregation_selector = meta.selector();
+
+		meta.create_gate("trust_aggregation", |v_cells| {
+			let s = v_cells.query_selector(aggregation_selector);
+
+			// acc_0 == 0. Without this, `acc_0` is a free witness and a
+			// dishonest prover could seed the accumulator with any offset,
+			// since the recurrence below only ever relates `acc_k` to
+			// `acc_{k+1}`, never `acc_0` to the constant `0`.
+			let acc_0 = v_cells.query_advice(common.advice[2], Rotation::cur());
+			let mut exprs = Vec::with_capacity(N + 2);
+			exprs.push(s.clone() * acc_0);
+
+			// Running accumulator: `acc_{k+1} = acc_k + c_jk * t_jk`, one
+			// multiplication and one addition constraint per neighbor, the
+			// way `AbsorbChip` threads its state across rows.
+			for k in 0..N {
+				let c_k = v_cells.query_advice(common.advice[0], Rotation(k as i32));
+				let t_k = v_cells.query_advice(common.advice[1], Rotation(k as i32));
+				let acc_k = v_cells.query_advice(common.advice[2], Rotation(k as i32));
+				let acc_next = v_cells.query_advice(common.advice[2], Rotation((k + 1) as i32));
+				exprs.push(s.clone() * (acc_next - (acc_k + c_k * t_k)));
+			}
+
+			// Final gate: `out = acc_N - SCALE^-1 * a * acc_N + a * p_i`, the
+			// `SCALE^2`-scaled form of `(1 - a) * acc_N + a * p_i` (see the
+			// type-level doc comment for the derivation).
+			let acc_n = v_cells.query_advice(common.advice[2], Rotation(N as i32));
+			let p_i = v_cells.query_advice(common.advice[3], Rotation(N as i32));
+			let a = v_cells.query_advice(common.advice[4], Rotation(N as i32));
+			let out = v_cells.query_advice(common.advice[2], Rotation((N + 1) as i32));
+			let scale_inv = Expression::Constant(Self::scale_inv());
+			exprs.push(
+				s * (out - (acc_n.clone() - scale_inv * a.clone() * acc_n.clone() + a * p_i)),
+			);
+
+			exprs
+		});
+
+		aggregation_selector
+	}
+
+	fn synthesize(
+		self, common: &CommonConfig, selector: &Selector, mut layouter: impl Layouter<F>,
+	) -> Result<Self::Output, Error> {
+		layouter.assign_region(
+			|| "trust_aggregation",
+			|region: Region<'_, F>| {
+				let mut ctx = RegionCtx::new(region, 0);
+				ctx.enable(selector.clone())?;
+
+				// acc_0 = 0
+				let mut acc = ctx.assign_advice(common.advice[2], Some(F::zero()))?;
+
+				for (c_cell, t_cell) in self.c.iter().zip(self.t.iter()) {
+					let c_k = ctx.copy_assign(common.advice[0], c_cell.clone())?;
+					let t_k = ctx.copy_assign(common.advice[1], t_cell.clone())?;
+
+					let next_acc = acc.value().and_then(|&acc_v| {
+						c_k.value().and_then(|&c_v| t_k.value().map(|&t_v| acc_v + c_v * t_v))
+					});
+
+					ctx.next();
+					acc = ctx.assign_advice(common.advice[2], next_acc)?;
+				}
+
+				let p_i = ctx.copy_assign(common.advice[3], self.p_i.clone())?;
+				let a = ctx.copy_assign(common.advice[4], self.a.clone())?;
+
+				let scale_inv = Self::scale_inv();
+				let out_value = acc.value().and_then(|&acc_v| {
+					a.value().and_then(|&a_v| {
+						p_i.value().map(|&p_v| acc_v - scale_inv * a_v * acc_v + a_v * p_v)
+					})
+				});
+
+				ctx.next();
+				let out = ctx.assign_advice(common.advice[2], out_value)?;
+
+				Ok(out)
+			},
+		)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use halo2::{
+		circuit::SimpleFloorPlanner,
+		dev::MockProver,
+		plonk::{Circuit, ConstraintSystem},
+	};
+	use pasta_curves::Fp;
+
+	const SCALE: u64 = 1_000_000;
+	const N: usize = 2;
+
+	#[derive(Clone)]
+	struct TestConfig {
+		common: CommonConfig,
+		trust_aggregation_selector: Selector,
+	}
+
+	/// Wraps `TrustAggregationChip` for `MockProver`. When `malicious_acc_0`
+	/// is set, bypasses the chip's own (honest) `acc_0 = 0` assignment and
+	/// hand-assigns the same region shape with a smuggled-in nonzero seed,
+	/// to check that the zero-pinning constraint actually rejects it.
+	struct TestCircuit {
+		c: [Fp; N],
+		t: [Fp; N],
+		p_i: Fp,
+		a: Fp,
+		malicious_acc_0: Option<Fp>,
+	}
+
+	impl Circuit<Fp> for TestCircuit {
+		type Config = TestConfig;
+		type FloorPlanner = SimpleFloorPlanner;
+
+		fn without_witnesses(&self) -> Self {
+			Self {
+				c: [Fp::zero(); N],
+				t: [Fp::zero(); N],
+				p_i: Fp::zero(),
+				a: Fp::zero(),
+				malicious_acc_0: None,
+			}
+		}
+
+		fn configure(meta: &mut ConstraintSystem<Fp>) -> TestConfig {
+			let common = CommonConfig::new(meta);
+			let trust_aggregation_selector =
+				TrustAggregationChip::<Fp, N, SCALE>::configure(&common, meta);
+			TestConfig { common, trust_aggregation_selector }
+		}
+
+		fn synthesize(
+			&self, config: TestConfig, mut layouter: impl Layouter<Fp>,
+		) -> Result<(), Error> {
+			let (c, t, p_i, a) = layouter.assign_region(
+				|| "assign_inputs",
+				|region: Region<'_, Fp>| {
+					let mut ctx = RegionCtx::new(region, 0);
+					let mut c_cells = Vec::with_capacity(N);
+					let mut t_cells = Vec::with_capacity(N);
+					for k in 0..N {
+						c_cells.push(ctx.assign_advice(config.common.advice[0], Some(self.c[k]))?);
+						t_cells.push(ctx.assign_advice(config.common.advice[1], Some(self.t[k]))?);
+						ctx.next();
+					}
+					let p_i = ctx.assign_advice(config.common.advice[3], Some(self.p_i))?;
+					let a = ctx.assign_advice(config.common.advice[4], Some(self.a))?;
+					Ok((c_cells, t_cells, p_i, a))
+				},
+			)?;
+			let c: [AssignedCell<Fp, Fp>; N] = c.try_into().unwrap();
+			let t: [AssignedCell<Fp, Fp>; N] = t.try_into().unwrap();
+
+			match self.malicious_acc_0 {
+				None => {
+					let chip = TrustAggregationChip::<Fp, N, SCALE>::new(c, t, p_i, a);
+					chip.synthesize(
+						&config.common,
+						&config.trust_aggregation_selector,
+						layouter.namespace(|| "trust_aggregation"),
+					)?;
+				}
+				Some(bad_acc_0) => layouter.assign_region(
+					|| "malicious_trust_aggregation",
+					|region: Region<'_, Fp>| {
+						let mut ctx = RegionCtx::new(region, 0);
+						ctx.enable(config.trust_aggregation_selector.clone())?;
+
+						let mut acc =
+							ctx.assign_advice(config.common.advice[2], Some(bad_acc_0))?;
+						for (c_cell, t_cell) in c.iter().zip(t.iter()) {
+							let c_k = ctx.copy_assign(config.common.advice[0], c_cell.clone())?;
+							let t_k = ctx.copy_assign(config.common.advice[1], t_cell.clone())?;
+							let next_acc = acc.value().and_then(|&acc_v| {
+								c_k.value()
+									.and_then(|&c_v| t_k.value().map(|&t_v| acc_v + c_v * t_v))
+							});
+							ctx.next();
+							acc = ctx.assign_advice(config.common.advice[2], next_acc)?;
+						}
+
+						let p_cell = ctx.copy_assign(config.common.advice[3], p_i.clone())?;
+						let a_cell = ctx.copy_assign(config.common.advice[4], a.clone())?;
+						let scale_inv = TrustAggregationChip::<Fp, N, SCALE>::scale_inv();
+						let out_value = acc.value().and_then(|&acc_v| {
+							a_cell.value().and_then(|&a_v| {
+								p_cell.value().map(|&p_v| acc_v - scale_inv * a_v * acc_v + a_v * p_v)
+							})
+						});
+						ctx.next();
+						ctx.assign_advice(config.common.advice[2], out_value)?;
+
+						Ok(())
+					},
+				)?,
+			}
+
+			Ok(())
+		}
+	}
+
+	fn scaled(value: f64) -> Fp {
+		Fp::from((value * SCALE as f64).round() as u64)
+	}
+
+	#[test]
+	fn test_trust_aggregation_satisfies_honest_witness() {
+		let circuit = TestCircuit {
+			c: [scaled(0.5), scaled(0.5)],
+			t: [scaled(0.4), scaled(0.2)],
+			p_i: scaled(0.3),
+			a: scaled(0.5),
+			malicious_acc_0: None,
+		};
+
+		let prover = MockProver::run(5, &circuit, vec![]).unwrap();
+		assert_eq!(prover.verify(), Ok(()));
+	}
+
+	#[test]
+	fn test_trust_aggregation_rejects_nonzero_initial_accumulator() {
+		let circuit = TestCircuit {
+			c: [scaled(0.5), scaled(0.5)],
+			t: [scaled(0.4), scaled(0.2)],
+			p_i: scaled(0.3),
+			a: scaled(0.5),
+			// A dishonest prover seeding the accumulator with a nonzero
+			// offset must be rejected by the `acc_0 == 0` constraint.
+			malicious_acc_0: Some(scaled(0.9)),
+		};
+
+		let prover = MockProver::run(5, &circuit, vec![]).unwrap();
+		assert!(prover.verify().is_err());
+	}
+}
@@ -0,0 +1,222 @@
+//! Aggregator that drives a whole peer set through repeated `heartbeat`
+//! rounds and reports network-wide convergence progress, instead of each
+//! `Peer` tracking only its own `is_converged` flag in isolation.
+
+use crate::peer::{Epoch, Peer, PeerConfig};
+
+/// A snapshot of network-wide convergence progress after a single round.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConvergenceSnapshot {
+	/// The epoch this snapshot was taken at.
+	pub epoch: Epoch,
+	/// Number of peers that have converged so far.
+	pub converged_count: usize,
+	/// The largest per-peer change in global trust score this round,
+	/// `max_i |t_i^k - t_i^{k-1}|`.
+	pub max_delta: f64,
+	/// Trust-weighted share of converged peers,
+	/// `Σ_i t_i * [converged_i] / Σ_i t_i`, so convergence among high-trust
+	/// peers counts more than convergence among fringe peers.
+	pub confidence: f64,
+}
+
+/// The report produced by [`Convergence::run`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConvergenceReport {
+	/// Number of rounds actually run before halting.
+	pub iterations: u64,
+	/// Per-round history, in order, one entry per completed round.
+	pub history: Vec<ConvergenceSnapshot>,
+}
+
+impl ConvergenceReport {
+	/// The final snapshot, if at least one round ran.
+	pub fn last(&self) -> Option<&ConvergenceSnapshot> {
+		self.history.last()
+	}
+}
+
+/// Drives a whole peer set through repeated `heartbeat` rounds and reports
+/// network-wide convergence progress.
+pub struct Convergence<C: PeerConfig> {
+	peers: Vec<Peer<C>>,
+	pre_trust_weight: f64,
+}
+
+impl<C: PeerConfig> Convergence<C> {
+	/// Create a new aggregator over `peers`, using `pre_trust_weight` for
+	/// every peer's `heartbeat` call.
+	pub fn new(peers: Vec<Peer<C>>, pre_trust_weight: f64) -> Self {
+		Self { peers, pre_trust_weight }
+	}
+
+	/// Access the current peer set.
+	pub fn peers(&self) -> &[Peer<C>] {
+		&self.peers
+	}
+
+	/// Run `heartbeat` for every peer, once per round, halting early once all
+	/// peers have converged or the largest per-peer delta drops below
+	/// `delta`. Returns a report carrying the full per-round history, so
+	/// callers can inspect the convergence trajectory.
+	pub fn run(&mut self, max_iters: u64, delta: f64) -> ConvergenceReport {
+		let mut history = Vec::new();
+
+		for epoch in 0..max_iters {
+			let before = self.peers.clone();
+
+			for peer in self.peers.iter_mut() {
+				peer.heartbeat(&before, delta, self.pre_trust_weight, epoch);
+			}
+
+			let snapshot = self.snapshot(epoch, &before);
+			let halt = snapshot.converged_count == self.peers.len() || snapshot.max_delta < delta;
+			history.push(snapshot);
+
+			if halt {
+				break;
+			}
+		}
+
+		ConvergenceReport { iterations: history.len() as u64, history }
+	}
+
+	/// Compute a [`ConvergenceSnapshot`] for the round that just moved the
+	/// peer set from `before` to its current state.
+	fn snapshot(&self, epoch: Epoch, before: &[Peer<C>]) -> ConvergenceSnapshot {
+		let converged_count = self.peers.iter().filter(|peer| peer.is_converged()).count();
+
+		let max_delta = self
+			.peers
+			.iter()
+			.zip(before.iter())
+			.map(|(after, before)| {
+				(after.get_global_trust_score() - before.get_global_trust_score()).abs()
+			})
+			.fold(0.0, f64::max);
+
+		let total_trust: f64 = self.peers.iter().map(|peer| peer.get_global_trust_score()).sum();
+		let converged_trust: f64 = self
+			.peers
+			.iter()
+			.filter(|peer| peer.is_converged())
+			.map(|peer| peer.get_global_trust_score())
+			.sum();
+		let confidence = if total_trust > 0.0 { converged_trust / total_trust } else { 0.0 };
+
+		ConvergenceSnapshot { epoch, converged_count, max_delta, confidence }
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[derive(Clone, Debug, PartialEq)]
+	struct TestConfig;
+	impl PeerConfig for TestConfig {
+		type Index = usize;
+	}
+
+	#[test]
+	fn test_run_converges_and_reports_history() {
+		let peers = vec![
+			Peer::<TestConfig>::new(0, 0.5, 0.5),
+			Peer::<TestConfig>::new(1, 0.5, 0.5),
+		];
+		let mut convergence = Convergence::new(peers, 0.5);
+
+		let report = convergence.run(10, 0.001);
+
+		assert!(report.iterations >= 1);
+		assert_eq!(report.history.len(), report.iterations as usize);
+		assert!(report.last().unwrap().converged_count <= convergence.peers().len());
+	}
+
+	#[test]
+	fn test_run_halts_when_all_peers_converged() {
+		let peers = vec![
+			Peer::<TestConfig>::new(0, 0.5, 0.5),
+			Peer::<TestConfig>::new(1, 0.5, 0.5),
+		];
+		let mut convergence = Convergence::new(peers, 0.5);
+
+		let report = convergence.run(1_000, 0.001);
+
+		assert!(report.iterations < 1_000);
+		assert_eq!(report.last().unwrap().converged_count, 2);
+	}
+
+	#[test]
+	fn test_run_matches_closed_form_fixed_point_for_asymmetric_peers() {
+		// Distinct pre-trust scores and no recorded interactions, so every
+		// peer's raw local trust towards any other falls back to that
+		// other peer's pre-trust score. With three peers, this naturally
+		// produces peers that converge on different rounds, which is
+		// exactly the case that silently drops a peer's opinions once it
+		// converges first.
+		let pre_trust = [0.6, 0.3, 0.1];
+		let pre_trust_weight = 0.5;
+
+		let peers: Vec<Peer<TestConfig>> = (0..3)
+			.map(|i| Peer::<TestConfig>::new(i, 0.0, pre_trust[i]))
+			.collect();
+		let mut convergence = Convergence::new(peers, pre_trust_weight);
+
+		let report = convergence.run(200, 1e-9);
+		assert_eq!(report.last().unwrap().converged_count, 3);
+
+		let expected = closed_form_fixed_point(&pre_trust, pre_trust_weight);
+		for (peer, expected_score) in convergence.peers().iter().zip(expected.iter()) {
+			let actual = peer.get_global_trust_score();
+			assert!(
+				(actual - expected_score).abs() < 1e-4,
+				"peer {}: expected {expected_score}, got {actual}",
+				peer.get_index(),
+			);
+		}
+	}
+
+	/// Solve the 3-peer EigenTrust fixed point `t = (1 - a) * C^T t + a * p`
+	/// directly, where `C_ji = p_i / (P - p_j)` is the normalized local trust
+	/// that falls out of the pre-trust-only fallback (no recorded
+	/// interactions). This is independent of `Convergence`/`Peer`, so
+	/// comparing against it catches regressions in the iterative algorithm.
+	fn closed_form_fixed_point(pre_trust: &[f64; 3], a: f64) -> [f64; 3] {
+		let total: f64 = pre_trust.iter().sum();
+		let c = |from: usize, to: usize| pre_trust[to] / (total - pre_trust[from]);
+
+		let mut matrix = [[0.0; 3]; 3];
+		for i in 0..3 {
+			matrix[i][i] = 1.0;
+			for j in 0..3 {
+				if i != j {
+					matrix[i][j] -= (1.0 - a) * c(j, i);
+				}
+			}
+		}
+		let b = [a * pre_trust[0], a * pre_trust[1], a * pre_trust[2]];
+
+		solve_3x3(matrix, b)
+	}
+
+	fn det_3x3(m: [[f64; 3]; 3]) -> f64 {
+		m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+			- m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+			+ m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+	}
+
+	/// Solve `matrix * x = b` for a 3x3 system via Cramer's rule.
+	fn solve_3x3(matrix: [[f64; 3]; 3], b: [f64; 3]) -> [f64; 3] {
+		let det = det_3x3(matrix);
+		let mut result = [0.0; 3];
+		for (col, slot) in result.iter_mut().enumerate() {
+			let mut replaced = matrix;
+			for row in 0..3 {
+				replaced[row][col] = b[row];
+			}
+			*slot = det_3x3(replaced) / det;
+		}
+		result
+	}
+}
@@ -3,19 +3,61 @@
 
 use ark_std::{collections::BTreeMap, fmt::Debug, hash::Hash, One, Zero};
 
+/// A discrete round index. Opinions are keyed by epoch so that a peer's
+/// trust history can be replayed instead of being overwritten each round.
+pub type Epoch = u64;
+
 /// Configuration trait for the Peer.
 pub trait PeerConfig: Clone {
 	/// Type for the Peer index.
 	type Index: From<usize> + Eq + Hash + Clone + Ord;
 }
 
+/// A peer's opinion about a neighbor, recorded for a specific epoch.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Opinion {
+	/// The epoch this opinion was recorded for.
+	pub epoch: Epoch,
+	/// The local trust score assigned to the neighbor for this epoch.
+	pub local_trust_score: f64,
+	/// The global trust score of the peer holding this opinion, at the time it was recorded.
+	pub global_trust_score: f64,
+	/// `local_trust_score * global_trust_score`, the precomputed contribution
+	/// this opinion makes to the neighbor's aggregated global trust score.
+	pub product: f64,
+}
+
+impl Opinion {
+	/// Create a new opinion, precomputing its `product`.
+	pub fn new(epoch: Epoch, local_trust_score: f64, global_trust_score: f64) -> Self {
+		Self {
+			epoch,
+			local_trust_score,
+			global_trust_score,
+			product: local_trust_score * global_trust_score,
+		}
+	}
+}
+
+/// Counts of successful and failed interactions observed with a neighbor,
+/// used to derive local trust from behavior instead of a caller-supplied score.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+struct Interactions {
+	/// Number of interactions that succeeded.
+	success: u32,
+	/// Number of interactions that failed.
+	failed: u32,
+}
+
 /// Peer structure.
 #[derive(Clone, Debug)]
 pub struct Peer<C: PeerConfig> {
 	/// The unique identifier of the peer.
 	index: C::Index,
-	/// Local trust scores of the peer towards other peers.
-	local_trust_scores: BTreeMap<C::Index, f64>,
+	/// Opinions held about other peers, keyed by peer index and then by epoch.
+	opinions: BTreeMap<C::Index, BTreeMap<Epoch, Opinion>>,
+	/// Observed interaction outcomes with other peers, keyed by peer index.
+	interactions: BTreeMap<C::Index, Interactions>,
 	/// Global trust score of the peer.
 	global_trust_score: f64,
 	/// Pre-trust score of the peer.
@@ -29,59 +71,140 @@ impl<C: PeerConfig> Peer<C> {
 	pub fn new(index: C::Index, global_trust_score: f64, pre_trust_score: f64) -> Self {
 		Self {
 			index,
-			local_trust_scores: BTreeMap::new(),
+			opinions: BTreeMap::new(),
+			interactions: BTreeMap::new(),
 			global_trust_score,
 			pre_trust_score,
 			is_converged: false,
 		}
 	}
 
-	/// Add a local trust score towards another peer.
-	pub fn add_neighbor(&mut self, peer_index: C::Index, local_trust_value: f64) {
-		self.local_trust_scores
-			.insert(peer_index, local_trust_value);
+	/// Record an opinion about a neighbor for a given epoch.
+	pub fn add_neighbor(&mut self, peer_index: C::Index, epoch: Epoch, local_trust_score: f64) {
+		let opinion = Opinion::new(epoch, local_trust_score, self.global_trust_score);
+		self.opinions
+			.entry(peer_index)
+			.or_insert_with(BTreeMap::new)
+			.insert(epoch, opinion);
+	}
+
+	/// Get the opinion held about a neighbor for a given epoch. Returns an
+	/// empty, zero-valued opinion when none was recorded, so missing data
+	/// never panics.
+	pub fn get_opinion(&self, peer_index: &C::Index, epoch: Epoch) -> Opinion {
+		self.opinions
+			.get(peer_index)
+			.and_then(|by_epoch| by_epoch.get(&epoch))
+			.copied()
+			.unwrap_or(Opinion { epoch, ..Opinion::default() })
+	}
+
+	/// Record the outcome of an interaction with a neighbor.
+	pub fn record_interaction(&mut self, peer_index: C::Index, succeeded: bool) {
+		let counter = self.interactions.entry(peer_index).or_default();
+		if succeeded {
+			counter.success += 1;
+		} else {
+			counter.failed += 1;
+		}
 	}
 
-	/// Calculate the global trust score.
-	pub fn heartbeat(&mut self, neighbors: &[Peer<C>], delta: f64, pre_trust_weight: f64) {
-		if self.is_converged {
-			return;
+	/// Get the raw (un-normalized) local trust towards `peer`, computed as the
+	/// ratio of successful to total interactions observed with it. Falls back
+	/// to the peer's pre-trust score when there have been no interactions yet.
+	pub fn get_raw_local_trust(&self, peer: &Peer<C>) -> f64 {
+		let Interactions { success, failed } =
+			self.interactions.get(&peer.index).copied().unwrap_or_default();
+
+		if success + failed > 0 {
+			f64::from(success) / f64::from(success + failed)
+		} else {
+			peer.get_pre_trust_score()
+		}
+	}
+
+	/// Get the normalized local trust towards `j`, `c(i,j) = s(i,j) / Σ_m s(i,m)`,
+	/// so that this peer's outgoing local trust across `all_peers` sums to one.
+	/// Falls back to the (normalized) pre-trust distribution when the raw
+	/// trust denominator is zero, or to zero if nobody is pre-trusted either.
+	pub fn normalized_local_trust(&self, j: &C::Index, all_peers: &[Peer<C>]) -> f64 {
+		let others = all_peers.iter().filter(|peer| peer.get_index() != self.index);
+
+		let total_raw_trust: f64 = others.clone().map(|peer| self.get_raw_local_trust(peer)).sum();
+		if total_raw_trust > 0.0 {
+			return others
+				.filter(|peer| peer.get_index() == *j)
+				.map(|peer| self.get_raw_local_trust(peer))
+				.sum::<f64>()
+				/ total_raw_trust;
+		}
+
+		let total_pre_trust: f64 = others.clone().map(|peer| peer.get_pre_trust_score()).sum();
+		if total_pre_trust > 0.0 {
+			others
+				.filter(|peer| peer.get_index() == *j)
+				.map(|peer| peer.get_pre_trust_score())
+				.sum::<f64>()
+				/ total_pre_trust
+		} else {
+			f64::zero()
+		}
+	}
+
+	/// Calculate the global trust score for a given epoch.
+	pub fn heartbeat(
+		&mut self, neighbors: &[Peer<C>], delta: f64, pre_trust_weight: f64, epoch: Epoch,
+	) {
+		// A converged peer's global trust score no longer needs recomputing,
+		// but it must keep publishing its (unchanged) opinion every epoch —
+		// otherwise `get_opinion` would default to zero for any epoch it
+		// stops publishing, and neighbors still converging would see its
+		// contribution silently vanish instead of staying frozen.
+		if !self.is_converged {
+			let mut new_global_trust_score = f64::zero();
+			for neighbor_j in neighbors.iter() {
+				// Skip if the neighbor is the same peer.
+				if self.index == neighbor_j.get_index() {
+					continue;
+				}
+
+				// Compute ti = `c_1i*t_1(k) + c_ji*t_z(k) + ... + c_ni*t_n(k)`
+				// Each neighbor's opinion of us for this epoch already carries its
+				// local trust towards us multiplied by its own global trust score,
+				// precomputed as `Opinion::product` when the opinion was recorded.
+				new_global_trust_score += neighbor_j.get_opinion(&self.index, epoch).product;
+			}
+
+			// (1 - a)*ti + a*p_i
+			// The new global trust score (ti) is taken into account.
+			// It is weighted by the `pre_trust_weight`, which dictates how seriously the
+			// pre-trust score is taken.
+			new_global_trust_score = (f64::one() - pre_trust_weight) * new_global_trust_score
+				+ pre_trust_weight * self.pre_trust_score;
+
+			// Converge if the difference between the new and old global trust score is less
+			// than delta.
+			let diff = (new_global_trust_score - self.global_trust_score).abs();
+			if diff <= delta {
+				self.is_converged = true;
+			}
+
+			self.global_trust_score = new_global_trust_score;
 		}
 
-		let mut new_global_trust_score = f64::zero();
+		// Publish our own opinion of each neighbor for the *next* epoch, using
+		// our normalized local trust towards them in place of the raw score,
+		// so neighbors can fold a properly stochastic opinion into their own
+		// global score once they reach that epoch.
 		for neighbor_j in neighbors.iter() {
-			// Skip if the neighbor is the same peer.
 			if self.index == neighbor_j.get_index() {
 				continue;
 			}
 
-			// Compute ti = `c_1i*t_1(k) + c_ji*t_z(k) + ... + c_ni*t_n(k)`
-			// We are going through each neighbor and taking their local trust
-			// towards peer `i`, and multiplying it by that neighbor's global trust score.
-			// This means that neighbors' opinion about peer i is weighted by their global
-			// trust score. If a neighbor has a low trust score (is not trusted by the
-			// network), their opinion is not taken seriously, compared to neighbors with a
-			// high trust score.
-			let neighbor_opinion =
-				neighbor_j.get_local_trust_score(&self.index) * neighbor_j.get_global_trust_score();
-			new_global_trust_score += neighbor_opinion;
-		}
-
-		// (1 - a)*ti + a*p_i
-		// The new global trust score (ti) is taken into account.
-		// It is weighted by the `pre_trust_weight`, which dictates how seriously the
-		// pre-trust score is taken.
-		new_global_trust_score = (f64::one() - pre_trust_weight) * new_global_trust_score
-			+ pre_trust_weight * self.pre_trust_score;
-
-		// Converge if the difference between the new and old global trust score is less
-		// than delta.
-		let diff = (new_global_trust_score - self.global_trust_score).abs();
-		if diff <= delta {
-			self.is_converged = true;
+			let normalized_local_trust =
+				self.normalized_local_trust(&neighbor_j.get_index(), neighbors);
+			self.add_neighbor(neighbor_j.get_index(), epoch + 1, normalized_local_trust);
 		}
-
-		self.global_trust_score = new_global_trust_score;
 	}
 
 	/// Check if the peer has converged.
@@ -103,11 +226,6 @@ impl<C: PeerConfig> Peer<C> {
 	pub fn get_index(&self) -> C::Index {
 		self.index.clone()
 	}
-
-	/// Get the local trust score of the peer towards another peer.
-	pub fn get_local_trust_score(&self, i: &C::Index) -> f64 {
-		self.local_trust_scores[i]
-	}
 }
 
 #[cfg(test)]
@@ -123,10 +241,94 @@ mod test {
 	#[test]
 	fn test_peer_new() {
 		let mut peer = Peer::<TestConfig>::new(0, 0.0, 0.4);
-		peer.add_neighbor(1, 0.5);
+		peer.add_neighbor(1, 0, 0.5);
 		assert_eq!(peer.get_index(), 0);
 		assert_eq!(peer.get_pre_trust_score(), 0.4);
 		assert_eq!(peer.get_global_trust_score(), 0.0);
-		assert_eq!(peer.get_local_trust_score(&1), 0.5);
+		assert_eq!(peer.get_opinion(&1, 0).local_trust_score, 0.5);
+	}
+
+	#[test]
+	fn test_raw_local_trust_falls_back_to_pre_trust() {
+		let peer = Peer::<TestConfig>::new(0, 0.0, 0.4);
+		let neighbor = Peer::<TestConfig>::new(1, 0.0, 0.7);
+		assert_eq!(peer.get_raw_local_trust(&neighbor), 0.7);
+	}
+
+	#[test]
+	fn test_raw_local_trust_from_interactions() {
+		let mut peer = Peer::<TestConfig>::new(0, 0.0, 0.4);
+		let neighbor = Peer::<TestConfig>::new(1, 0.0, 0.7);
+		peer.record_interaction(1, true);
+		peer.record_interaction(1, true);
+		peer.record_interaction(1, false);
+		assert_eq!(peer.get_raw_local_trust(&neighbor), 2.0 / 3.0);
+	}
+
+	#[test]
+	fn test_normalized_local_trust_sums_to_one() {
+		let mut peer = Peer::<TestConfig>::new(0, 0.0, 0.4);
+		peer.record_interaction(1, true);
+		peer.record_interaction(1, false);
+		peer.record_interaction(1, false);
+		peer.record_interaction(1, false);
+		peer.record_interaction(2, true);
+		peer.record_interaction(2, true);
+		peer.record_interaction(2, true);
+		peer.record_interaction(2, false);
+
+		let all_peers = vec![
+			Peer::<TestConfig>::new(0, 0.0, 0.4),
+			Peer::<TestConfig>::new(1, 0.0, 0.0),
+			Peer::<TestConfig>::new(2, 0.0, 0.0),
+		];
+
+		let c1 = peer.normalized_local_trust(&1, &all_peers);
+		let c2 = peer.normalized_local_trust(&2, &all_peers);
+		assert_eq!(c1, 0.25);
+		assert_eq!(c2, 0.75);
+		assert_eq!(c1 + c2, 1.0);
+	}
+
+	#[test]
+	fn test_converged_peer_keeps_publishing_opinions() {
+		let peer1 = Peer::<TestConfig>::new(1, 0.5, 0.5);
+		let mut peer0 = Peer::<TestConfig>::new(0, 0.5, 0.5);
+
+		// A huge delta converges peer 0 on its very first heartbeat.
+		peer0.heartbeat(&[peer0.clone(), peer1.clone()], 1.0, 0.5, 0);
+		assert!(peer0.is_converged());
+		let opinion_epoch_1 = peer0.get_opinion(&1, 1);
+		assert!(opinion_epoch_1.product > 0.0);
+
+		// Peer 0 is converged, but it must still publish an opinion for the
+		// next epoch instead of silently defaulting to zero.
+		peer0.heartbeat(&[peer0.clone(), peer1], 1.0, 0.5, 1);
+		let opinion_epoch_2 = peer0.get_opinion(&1, 2);
+		assert_eq!(opinion_epoch_2.local_trust_score, opinion_epoch_1.local_trust_score);
+		assert_eq!(opinion_epoch_2.product, opinion_epoch_1.product);
+	}
+
+	#[test]
+	fn test_normalized_local_trust_falls_back_to_zero_without_pre_trust() {
+		let peer = Peer::<TestConfig>::new(0, 0.0, 0.4);
+		let all_peers = vec![
+			Peer::<TestConfig>::new(0, 0.0, 0.4),
+			Peer::<TestConfig>::new(1, 0.0, 0.0),
+			Peer::<TestConfig>::new(2, 0.0, 0.0),
+		];
+
+		// No interactions and no pre-trust among the neighbors: falls back to zero.
+		assert_eq!(peer.normalized_local_trust(&1, &all_peers), 0.0);
+	}
+
+	#[test]
+	fn test_get_opinion_missing_is_zero() {
+		let peer = Peer::<TestConfig>::new(0, 0.0, 0.4);
+		let opinion = peer.get_opinion(&1, 3);
+		assert_eq!(opinion.epoch, 3);
+		assert_eq!(opinion.local_trust_score, 0.0);
+		assert_eq!(opinion.global_trust_score, 0.0);
+		assert_eq!(opinion.product, 0.0);
 	}
 }
@@ -0,0 +1,114 @@
+//! Trust-weighted neighbor selection. Peers with a higher global trust score
+//! are more likely to be picked, which is useful for gossip, query routing, or
+//! service selection layered on top of the trust graph. Selection is seeded
+//! and deterministic so results are reproducible.
+
+use crate::peer::{Peer, PeerConfig};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+
+/// Floor applied to a peer's weight so that zero-trust peers remain
+/// selectable with a tiny probability instead of being starved entirely.
+const MIN_WEIGHT: f64 = 1e-9;
+
+/// Build the cumulative weight array `w_k = max(MIN_WEIGHT, global_trust_score_k)`
+/// alongside the total weight.
+fn cumulative_weights<C: PeerConfig>(peers: &[Peer<C>]) -> (Vec<f64>, f64) {
+	let mut total = 0.0;
+	let cumulative = peers
+		.iter()
+		.map(|peer| {
+			total += peer.get_global_trust_score().max(MIN_WEIGHT);
+			total
+		})
+		.collect();
+	(cumulative, total)
+}
+
+/// Binary-search `cumulative` for the first entry greater than `target`.
+fn sample_index(cumulative: &[f64], target: f64) -> usize {
+	cumulative.partition_point(|&weight| weight <= target).min(cumulative.len() - 1)
+}
+
+/// Sample a single peer index with probability proportional to its global
+/// trust score. Returns `None` if `peers` is empty.
+pub fn weighted_sample_single<C: PeerConfig>(peers: &[Peer<C>], seed: u64) -> Option<C::Index> {
+	if peers.is_empty() {
+		return None;
+	}
+
+	let mut rng = ChaCha20Rng::seed_from_u64(seed);
+	let (cumulative, total) = cumulative_weights(peers);
+	let target = rng.gen_range(0.0..total);
+
+	Some(peers[sample_index(&cumulative, target)].get_index())
+}
+
+/// Produce a full trust-weighted ordering of `peers`, by repeatedly sampling
+/// without replacement and renormalizing over what remains.
+pub fn weighted_shuffle<C: PeerConfig>(peers: &[Peer<C>], seed: u64) -> Vec<C::Index> {
+	let mut rng = ChaCha20Rng::seed_from_u64(seed);
+	let mut remaining: Vec<Peer<C>> = peers.to_vec();
+	let mut ordering = Vec::with_capacity(peers.len());
+
+	while !remaining.is_empty() {
+		let (cumulative, total) = cumulative_weights(&remaining);
+		let target = rng.gen_range(0.0..total);
+		let index = sample_index(&cumulative, target);
+
+		ordering.push(remaining.remove(index).get_index());
+	}
+
+	ordering
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[derive(Clone, Debug, PartialEq)]
+	struct TestConfig;
+	impl PeerConfig for TestConfig {
+		type Index = usize;
+	}
+
+	#[test]
+	fn test_weighted_sample_single_empty() {
+		let peers: Vec<Peer<TestConfig>> = vec![];
+		assert_eq!(weighted_sample_single(&peers, 0), None);
+	}
+
+	#[test]
+	fn test_weighted_sample_single_picks_a_peer() {
+		let peers = vec![
+			Peer::<TestConfig>::new(0, 0.9, 0.0),
+			Peer::<TestConfig>::new(1, 0.1, 0.0),
+			Peer::<TestConfig>::new(2, 0.0, 0.0),
+		];
+		let picked = weighted_sample_single(&peers, 42).unwrap();
+		assert!(peers.iter().any(|peer| peer.get_index() == picked));
+	}
+
+	#[test]
+	fn test_weighted_sample_single_is_deterministic() {
+		let peers = vec![
+			Peer::<TestConfig>::new(0, 0.9, 0.0),
+			Peer::<TestConfig>::new(1, 0.1, 0.0),
+		];
+		let a = weighted_sample_single(&peers, 7);
+		let b = weighted_sample_single(&peers, 7);
+		assert_eq!(a, b);
+	}
+
+	#[test]
+	fn test_weighted_shuffle_is_a_permutation() {
+		let peers = vec![
+			Peer::<TestConfig>::new(0, 0.9, 0.0),
+			Peer::<TestConfig>::new(1, 0.5, 0.0),
+			Peer::<TestConfig>::new(2, 0.1, 0.0),
+		];
+		let mut ordering = weighted_shuffle(&peers, 1);
+		ordering.sort_unstable();
+		assert_eq!(ordering, vec![0, 1, 2]);
+	}
+}